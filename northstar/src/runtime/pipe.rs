@@ -13,110 +13,179 @@
 //   limitations under the License.
 
 use futures::ready;
-use nix::unistd;
+use nix::{
+    sys::socket::{self, AddressFamily, ControlMessage, ControlMessageOwned, MsgFlags, SockFlag, SockType},
+    sys::uio::IoVec,
+    unistd,
+};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     convert::TryFrom,
     io,
     io::Result,
     mem,
+    os::fd::{AsFd, BorrowedFd, FromRawFd, OwnedFd},
     os::unix::io::{AsRawFd, IntoRawFd, RawFd},
     pin::Pin,
     task::{Context, Poll},
 };
-use tokio::io::{unix::AsyncFd, AsyncRead, AsyncWrite, ReadBuf};
+use tokio::io::{unix::AsyncFd, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
 
 /// Opens a pipe(2) with both ends blocking
 pub(crate) fn pipe() -> Result<(PipeRead, PipeWrite)> {
-    unistd::pipe()
-        .map_err(from_nix)
-        .map(|(read, write)| (PipeRead { fd: read }, PipeWrite { fd: write }))
+    unistd::pipe().map_err(from_nix).map(|(read, write)| {
+        // Safety: `read`/`write` are fresh fds just returned by `pipe(2)`, owned by
+        // nobody else yet.
+        unsafe {
+            (
+                PipeRead {
+                    fd: OwnedFd::from_raw_fd(read),
+                },
+                PipeWrite {
+                    fd: OwnedFd::from_raw_fd(write),
+                },
+            )
+        }
+    })
 }
 
 /// Read end of a pipe(2)
+///
+/// The fd is owned via `OwnedFd`, so closing and double-close safety are handled
+/// by the standard library instead of a hand-written `Drop`.
 #[derive(Debug)]
 pub(crate) struct PipeRead {
-    fd: RawFd,
+    fd: OwnedFd,
 }
 
 impl io::Read for PipeRead {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-        unistd::read(self.fd, buf).map_err(from_nix)
+        unistd::read(self.fd.as_raw_fd(), buf).map_err(from_nix)
     }
 }
 
 impl AsRawFd for PipeRead {
     fn as_raw_fd(&self) -> RawFd {
-        self.fd
+        self.fd.as_raw_fd()
+    }
+}
+
+impl AsFd for PipeRead {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
     }
 }
 
 impl IntoRawFd for PipeRead {
     fn into_raw_fd(self) -> RawFd {
-        let fd = self.fd;
-        mem::forget(self);
-        fd
+        self.fd.into_raw_fd()
     }
 }
 
-impl Drop for PipeRead {
-    fn drop(&mut self) {
-        // Ignore close errors
-        unistd::close(self.fd).ok();
+impl FromRawFd for PipeRead {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        PipeRead {
+            fd: OwnedFd::from_raw_fd(fd),
+        }
     }
 }
 
 /// Write end of a pipe(2)
+///
+/// The fd is owned via `OwnedFd`, so closing and double-close safety are handled
+/// by the standard library instead of a hand-written `Drop`.
 #[derive(Debug)]
 pub(crate) struct PipeWrite {
-    fd: RawFd,
+    fd: OwnedFd,
 }
 
 impl io::Write for PipeWrite {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
-        unistd::write(self.fd, buf).map_err(from_nix)
+        unistd::write(self.fd.as_raw_fd(), buf).map_err(from_nix)
     }
 
     fn flush(&mut self) -> Result<()> {
-        unistd::fsync(self.fd).map_err(from_nix)
+        unistd::fsync(self.fd.as_raw_fd()).map_err(from_nix)
     }
 }
 
 impl AsRawFd for PipeWrite {
     fn as_raw_fd(&self) -> RawFd {
-        self.fd
+        self.fd.as_raw_fd()
+    }
+}
+
+impl AsFd for PipeWrite {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
     }
 }
 
 impl IntoRawFd for PipeWrite {
     fn into_raw_fd(self) -> RawFd {
-        let fd = self.fd;
-        mem::forget(self);
-        fd
+        self.fd.into_raw_fd()
     }
 }
 
-impl Drop for PipeWrite {
-    fn drop(&mut self) {
-        // Ignore close errors
-        unistd::close(self.fd).ok();
+impl FromRawFd for PipeWrite {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        PipeWrite {
+            fd: OwnedFd::from_raw_fd(fd),
+        }
     }
 }
 
+/// Selects the I/O strategy used by [`AsyncPipeRead`] and [`AsyncPipeWrite`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Backend {
+    /// Set the fd nonblocking and poll it for readiness with tokio's `AsyncFd`,
+    /// reissuing `read(2)`/`write(2)` on each wakeup. The default, and the only choice when
+    /// the running kernel lacks io_uring.
+    AsyncFd,
+    /// Submit `IORING_OP_READ`/`IORING_OP_WRITE` SQEs against the fd and complete
+    /// once the matching CQE arrives, moving bytes in a single submit/complete
+    /// cycle instead of a syscall per readiness event.
+    #[cfg(feature = "io-uring")]
+    IoUring,
+}
+
 /// Pipe's synchronous reading end
 #[derive(Debug)]
 pub(crate) struct AsyncPipeRead {
-    inner: AsyncFd<PipeRead>,
+    inner: ReadInner,
+}
+
+#[derive(Debug)]
+enum ReadInner {
+    AsyncFd(AsyncFd<PipeRead>),
+    #[cfg(feature = "io-uring")]
+    IoUring(io_uring_backend::UringRead),
+}
+
+impl AsyncPipeRead {
+    /// Wraps `reader` using the given [`Backend`].
+    pub(crate) fn with_backend(reader: PipeRead, backend: Backend) -> Result<Self> {
+        match backend {
+            Backend::AsyncFd => {
+                reader.set_nonblocking();
+                Ok(AsyncPipeRead {
+                    inner: ReadInner::AsyncFd(AsyncFd::new(reader)?),
+                })
+            }
+            #[cfg(feature = "io-uring")]
+            Backend::IoUring => Ok(AsyncPipeRead {
+                // io_uring operates on the fd directly: it must stay blocking.
+                inner: ReadInner::IoUring(io_uring_backend::UringRead::new(reader)?),
+            }),
+        }
+    }
 }
 
 impl TryFrom<PipeRead> for AsyncPipeRead {
     type Error = io::Error;
 
     fn try_from(reader: PipeRead) -> Result<Self> {
-        reader.set_nonblocking();
-        Ok(AsyncPipeRead {
-            inner: AsyncFd::new(reader)?,
-        })
+        Self::with_backend(reader, Backend::AsyncFd)
     }
 }
 
@@ -126,27 +195,31 @@ impl AsyncRead for AsyncPipeRead {
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<Result<()>> {
-        loop {
-            let mut guard = ready!(self.inner.poll_read_ready(cx))?;
-            match guard.try_io(|inner| {
-                let fd = inner.get_ref().as_raw_fd();
-                // map nix::Error to io::Error
-                match unistd::read(fd, buf.initialized_mut()) {
-                    Ok(n) => Ok(n),
-                    // read(2) on a nonblocking file (O_NONBLOCK) returns EAGAIN or EWOULDBLOCK in
-                    // case that the read would block. That case is handled by `try_io`.
-                    Err(e) => Err(from_nix(e)),
-                }
-            }) {
-                Ok(Ok(n)) => {
-                    buf.advance(n);
-                    return Poll::Ready(Ok(()));
-                }
-                Ok(Err(e)) => {
-                    return Poll::Ready(Err(e));
+        match &mut self.get_mut().inner {
+            ReadInner::AsyncFd(inner) => loop {
+                let mut guard = ready!(inner.poll_read_ready(cx))?;
+                match guard.try_io(|inner| {
+                    let fd = inner.get_ref().as_raw_fd();
+                    // map nix::Error to io::Error
+                    match unistd::read(fd, buf.initialized_mut()) {
+                        Ok(n) => Ok(n),
+                        // read(2) on a nonblocking file (O_NONBLOCK) returns EAGAIN or EWOULDBLOCK in
+                        // case that the read would block. That case is handled by `try_io`.
+                        Err(e) => Err(from_nix(e)),
+                    }
+                }) {
+                    Ok(Ok(n)) => {
+                        buf.advance(n);
+                        return Poll::Ready(Ok(()));
+                    }
+                    Ok(Err(e)) => {
+                        return Poll::Ready(Err(e));
+                    }
+                    Err(_would_block) => continue,
                 }
-                Err(_would_block) => continue,
-            }
+            },
+            #[cfg(feature = "io-uring")]
+            ReadInner::IoUring(inner) => inner.poll_read(cx, buf),
         }
     }
 }
@@ -154,28 +227,56 @@ impl AsyncRead for AsyncPipeRead {
 /// Pipe's asynchronous writing end
 #[derive(Debug)]
 pub(crate) struct AsyncPipeWrite {
-    inner: AsyncFd<PipeWrite>,
+    inner: WriteInner,
+}
+
+#[derive(Debug)]
+enum WriteInner {
+    AsyncFd(AsyncFd<PipeWrite>),
+    #[cfg(feature = "io-uring")]
+    IoUring(io_uring_backend::UringWrite),
+}
+
+impl AsyncPipeWrite {
+    /// Wraps `write` using the given [`Backend`].
+    pub(crate) fn with_backend(write: PipeWrite, backend: Backend) -> Result<Self> {
+        match backend {
+            Backend::AsyncFd => {
+                write.set_nonblocking();
+                Ok(AsyncPipeWrite {
+                    inner: WriteInner::AsyncFd(AsyncFd::new(write)?),
+                })
+            }
+            #[cfg(feature = "io-uring")]
+            Backend::IoUring => Ok(AsyncPipeWrite {
+                inner: WriteInner::IoUring(io_uring_backend::UringWrite::new(write)?),
+            }),
+        }
+    }
 }
 
 impl TryFrom<PipeWrite> for AsyncPipeWrite {
     type Error = io::Error;
 
     fn try_from(write: PipeWrite) -> Result<Self> {
-        write.set_nonblocking();
-        Ok(AsyncPipeWrite {
-            inner: AsyncFd::new(write)?,
-        })
+        Self::with_backend(write, Backend::AsyncFd)
     }
 }
 
 impl AsyncWrite for AsyncPipeWrite {
     fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
-        loop {
-            let mut guard = ready!(self.inner.poll_write_ready(cx))?;
-            match guard.try_io(|inner| unistd::write(inner.as_raw_fd(), buf).map_err(from_nix)) {
-                Ok(result) => return Poll::Ready(result),
-                Err(_would_block) => continue,
-            }
+        match &mut self.get_mut().inner {
+            WriteInner::AsyncFd(inner) => loop {
+                let mut guard = ready!(inner.poll_write_ready(cx))?;
+                match guard
+                    .try_io(|inner| unistd::write(inner.as_raw_fd(), buf).map_err(from_nix))
+                {
+                    Ok(result) => return Poll::Ready(result),
+                    Err(_would_block) => continue,
+                }
+            },
+            #[cfg(feature = "io-uring")]
+            WriteInner::IoUring(inner) => inner.poll_write(cx, buf),
         }
     }
 
@@ -188,6 +289,428 @@ impl AsyncWrite for AsyncPipeWrite {
     }
 }
 
+/// io_uring-backed implementation of [`Backend::IoUring`].
+///
+/// Kept in its own module since it pulls in the `io-uring` crate, which is only
+/// available behind the `io-uring` feature.
+#[cfg(feature = "io-uring")]
+mod io_uring_backend {
+    use super::{from_nix, PipeRead, PipeWrite};
+    use futures::ready;
+    use io_uring::{opcode, types, IoUring};
+    use nix::unistd;
+    use std::{
+        io::{self, Result},
+        os::unix::io::{AsRawFd, RawFd},
+        task::{Context, Poll},
+    };
+    use tokio::io::{unix::AsyncFd, ReadBuf};
+
+    /// An eventfd(2), wrapped so it can be polled with tokio's `AsyncFd`.
+    ///
+    /// io_uring completions don't come with a pollable fd of their own; registering
+    /// an eventfd with `IORING_REGISTER_EVENTFD` makes the kernel bump it whenever a
+    /// CQE is posted, which is what we actually wait on.
+    #[derive(Debug)]
+    struct EventFd(RawFd);
+
+    impl AsRawFd for EventFd {
+        fn as_raw_fd(&self) -> RawFd {
+            self.0
+        }
+    }
+
+    impl Drop for EventFd {
+        fn drop(&mut self) {
+            unistd::close(self.0).ok();
+        }
+    }
+
+    /// Size of the buffer each `Ring` owns for its in-flight operation.
+    ///
+    /// Bounds how much a single poll can move; callers loop (as `AsyncRead`/
+    /// `AsyncWrite` already require) for anything larger.
+    const URING_CHUNK_SIZE: usize = 64 * 1024;
+
+    /// A single-fd io_uring ring with its completion eventfd wired up for polling.
+    ///
+    /// Submitted SQEs point at `Ring`'s own `buf`, never at a caller-supplied
+    /// buffer: the future polling this ring can be dropped (cancelled) at any
+    /// await point while the kernel still holds the submitted pointer, and a
+    /// caller's buffer is free to go away the moment its future is dropped. By
+    /// only ever handing the kernel a pointer into memory `Ring` itself owns,
+    /// and blocking for the in-flight op to finish before that memory is freed
+    /// (see `Drop`), there is no window where the kernel can write into freed
+    /// memory.
+    ///
+    /// A read completion can fill more of `buf` than a caller's `ReadBuf` has
+    /// room for, so `buf[..filled]` is retained across polls and handed out a
+    /// `ReadBuf`'s-worth at a time via `drain_buffered`; `consumed` is the
+    /// cursor into it.
+    struct Ring {
+        ring: IoUring,
+        event: AsyncFd<EventFd>,
+        in_flight: bool,
+        buf: Box<[u8; URING_CHUNK_SIZE]>,
+        filled: usize,
+        consumed: usize,
+    }
+
+    impl Ring {
+        fn new() -> Result<Self> {
+            let ring = IoUring::new(8).map_err(uring_err)?;
+            let fd = nix::sys::eventfd::eventfd(0, nix::sys::eventfd::EfdFlags::EFD_NONBLOCK)
+                .map_err(from_nix)?;
+            ring.submitter().register_eventfd(fd).map_err(uring_err)?;
+            Ok(Ring {
+                ring,
+                event: AsyncFd::new(EventFd(fd))?,
+                in_flight: false,
+                buf: Box::new([0u8; URING_CHUNK_SIZE]),
+                filled: 0,
+                consumed: 0,
+            })
+        }
+
+        /// True if a prior read completion left bytes in `buf` that haven't
+        /// been handed to the caller yet.
+        fn has_buffered(&self) -> bool {
+            self.consumed < self.filled
+        }
+
+        /// Hands out up to `max` bytes left over from the last read
+        /// completion, advancing the consumed cursor.
+        fn drain_buffered(&mut self, max: usize) -> &[u8] {
+            let n = (self.filled - self.consumed).min(max);
+            let start = self.consumed;
+            self.consumed += n;
+            &self.buf[start..start + n]
+        }
+
+        /// Records a new read completion of `n` bytes as unconsumed.
+        fn fill(&mut self, n: usize) {
+            self.filled = n;
+            self.consumed = 0;
+        }
+
+        /// Submits a read of up to `Ring::buf`'s capacity from `fd`, if no
+        /// operation is currently in flight for this ring.
+        fn submit_read_if_idle(&mut self, fd: RawFd) -> Result<()> {
+            if !self.in_flight {
+                let entry = opcode::Read::new(
+                    types::Fd(fd),
+                    self.buf.as_mut_ptr(),
+                    self.buf.len() as u32,
+                )
+                .build()
+                .user_data(0);
+                // Safety: the SQE points at `self.buf`, owned by this `Ring` for as
+                // long as the operation may be in flight; see the struct doc.
+                unsafe {
+                    self.ring.submission().push(&entry).map_err(uring_err)?;
+                }
+                self.ring.submit().map_err(uring_err)?;
+                self.in_flight = true;
+            }
+            Ok(())
+        }
+
+        /// Copies up to `Ring::buf`'s capacity of `data` in, then submits a write
+        /// of it to `fd`, if no operation is currently in flight for this ring.
+        /// Returns the number of bytes accepted from `data` this call (0 if an
+        /// operation was already in flight).
+        fn submit_write_if_idle(&mut self, fd: RawFd, data: &[u8]) -> Result<usize> {
+            if self.in_flight {
+                return Ok(0);
+            }
+            let n = data.len().min(self.buf.len());
+            self.buf[..n].copy_from_slice(&data[..n]);
+            let entry = opcode::Write::new(types::Fd(fd), self.buf.as_ptr(), n as u32)
+                .build()
+                .user_data(0);
+            // Safety: see `submit_read_if_idle`.
+            unsafe {
+                self.ring.submission().push(&entry).map_err(uring_err)?;
+            }
+            self.ring.submit().map_err(uring_err)?;
+            self.in_flight = true;
+            Ok(n)
+        }
+
+        /// Drains the eventfd counter, then the completion queue, returning the
+        /// result of the in-flight operation once its CQE is observed.
+        fn poll_completion(&mut self, cx: &mut Context<'_>) -> Poll<Result<i32>> {
+            loop {
+                let mut guard = ready!(self.event.poll_read_ready(cx))?;
+                let mut count = [0u8; 8];
+                match guard.try_io(|event| unistd::read(event.as_raw_fd(), &mut count).map_err(from_nix))
+                {
+                    Ok(_) => {}
+                    Err(_would_block) => continue,
+                }
+                if let Some(cqe) = self.ring.completion().next() {
+                    self.in_flight = false;
+                    let result = cqe.result();
+                    return Poll::Ready(if result < 0 {
+                        Err(io::Error::from_raw_os_error(-result))
+                    } else {
+                        Ok(result)
+                    });
+                }
+            }
+        }
+    }
+
+    impl Drop for Ring {
+        fn drop(&mut self) {
+            if self.in_flight {
+                // Block until the kernel posts the CQE for the in-flight op before
+                // `buf` is freed, so it never observes a dangling pointer; see the
+                // struct doc. This is the one place we deliberately block in an
+                // async path: it only triggers on cancellation, not the steady
+                // state, and there is no sound way to free `buf` out from under a
+                // submission we can't un-submit.
+                self.ring.submit_and_wait(1).ok();
+                self.ring.completion().next();
+            }
+        }
+    }
+
+    fn uring_err(e: impl std::fmt::Display) -> io::Error {
+        io::Error::new(io::ErrorKind::Other, e.to_string())
+    }
+
+    /// io_uring-backed read end. The fd is kept blocking: io_uring performs the
+    /// read itself, there is no readiness loop reissuing the syscall.
+    pub(super) struct UringRead {
+        // Must drop before `reader`, so the ring's in-flight op against `reader`'s
+        // fd is resolved before the fd is closed; see `Ring`'s doc.
+        ring: Ring,
+        reader: PipeRead,
+    }
+
+    impl std::fmt::Debug for UringRead {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("UringRead").field("reader", &self.reader).finish()
+        }
+    }
+
+    impl UringRead {
+        pub(super) fn new(reader: PipeRead) -> Result<Self> {
+            Ok(UringRead {
+                ring: Ring::new()?,
+                reader,
+            })
+        }
+
+        pub(super) fn poll_read(
+            &mut self,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<Result<()>> {
+            if !self.ring.has_buffered() {
+                self.ring.submit_read_if_idle(self.reader.as_raw_fd())?;
+                let n = ready!(self.ring.poll_completion(cx))? as usize;
+                self.ring.fill(n);
+                if n == 0 {
+                    // EOF: nothing buffered to drain below.
+                    return Poll::Ready(Ok(()));
+                }
+            }
+            // A completion can fill more of the ring's buffer than `buf` has
+            // room for, so only drain as much as `buf` can take; the rest
+            // stays buffered for the next poll.
+            let chunk = self.ring.drain_buffered(buf.remaining());
+            buf.put_slice(chunk);
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    /// io_uring-backed write end. The fd is kept blocking, mirroring [`UringRead`].
+    pub(super) struct UringWrite {
+        // See `UringRead` on field order.
+        ring: Ring,
+        writer: PipeWrite,
+    }
+
+    impl std::fmt::Debug for UringWrite {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("UringWrite").field("writer", &self.writer).finish()
+        }
+    }
+
+    impl UringWrite {
+        pub(super) fn new(writer: PipeWrite) -> Result<Self> {
+            Ok(UringWrite {
+                ring: Ring::new()?,
+                writer,
+            })
+        }
+
+        pub(super) fn poll_write(
+            &mut self,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<Result<usize>> {
+            self.ring.submit_write_if_idle(self.writer.as_raw_fd(), buf)?;
+            let n = ready!(self.ring.poll_completion(cx))?;
+            Poll::Ready(Ok(n as usize))
+        }
+    }
+}
+
+/// Maximum accepted frame size for `AsyncPipeSendRecv::recv`, guarding against
+/// allocating an arbitrarily large buffer for a corrupt or malicious length
+/// prefix.
+const ASYNC_PIPE_MAX_FRAME_SIZE: u32 = 1024 * 1024;
+
+/// Length-delimited, typed message channel over `AsyncPipeRead`/`AsyncPipeWrite`.
+///
+/// `PipeSend`/`PipeRecv` only work on synchronous `io::Read`/`io::Write` and rely
+/// on `bincode::deserialize_from` reading straight off the fd, which doesn't work
+/// on a nonblocking pipe. This frames each message with a 4-byte little-endian
+/// length prefix so `send`/`recv` stay correct across partial reads and writes.
+#[derive(Debug)]
+pub(crate) struct AsyncPipeSendRecv {
+    read: AsyncPipeRead,
+    write: AsyncPipeWrite,
+}
+
+impl AsyncPipeSendRecv {
+    pub(crate) fn new(read: AsyncPipeRead, write: AsyncPipeWrite) -> Self {
+        AsyncPipeSendRecv { read, write }
+    }
+
+    /// Serializes `item` with bincode and writes it prefixed with its length.
+    pub(crate) async fn send<T: Serialize>(&mut self, item: T) -> Result<()> {
+        let payload =
+            bincode::serialize(&item).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        let len = u32::try_from(payload.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        if len > ASYNC_PIPE_MAX_FRAME_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "frame of {} bytes exceeds the {} byte limit",
+                    len, ASYNC_PIPE_MAX_FRAME_SIZE
+                ),
+            ));
+        }
+        self.write.write_all(&len.to_le_bytes()).await?;
+        self.write.write_all(&payload).await?;
+        Ok(())
+    }
+
+    /// Reads a length-prefixed message and deserializes it with bincode.
+    ///
+    /// A close of the write end at a message boundary surfaces here as a clean
+    /// `UnexpectedEof` on the length prefix, since `read_exact` reads zero bytes
+    /// before filling any of it - callers can match on that to tell the peer went
+    /// away from an actual I/O failure.
+    pub(crate) async fn recv<T: DeserializeOwned>(&mut self) -> Result<T> {
+        let mut len_buf = [0u8; 4];
+        self.read.read_exact(&mut len_buf).await?;
+        let len = u32::from_le_bytes(len_buf);
+        if len > ASYNC_PIPE_MAX_FRAME_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "frame of {} bytes exceeds the {} byte limit",
+                    len, ASYNC_PIPE_MAX_FRAME_SIZE
+                ),
+            ));
+        }
+
+        let mut payload = vec![0u8; len as usize];
+        self.read.read_exact(&mut payload).await?;
+        bincode::deserialize(&payload).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+/// Opens a pidfd for `pid` via `pidfd_open(2)`.
+///
+/// `nix` has no wrapper for this syscall, so it's issued directly.
+fn pidfd_open(pid: unistd::Pid) -> Result<RawFd> {
+    let fd = unsafe { nix::libc::syscall(nix::libc::SYS_pidfd_open, pid.as_raw(), 0) };
+    if fd < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(fd as RawFd)
+    }
+}
+
+/// How `ChildChannel::wait` observes the child's exit.
+#[derive(Debug)]
+enum WaitStrategy {
+    /// Poll a pidfd for readiness; it becomes readable once the child exits.
+    PidFd(AsyncFd<OwnedFd>),
+    /// `pidfd_open` isn't available on this kernel: block in `waitpid` on a
+    /// dedicated thread instead.
+    BlockingThread,
+}
+
+/// Supervises a forked child: a typed control channel plus a way to await the
+/// child's exit without blocking in `waitpid`.
+///
+/// Control messages flow over an `AsyncPipeSendRecv`; `wait()` resolves once the
+/// child's pidfd signals readiness (or, on kernels without `pidfd_open`, once a
+/// blocking-thread `waitpid` completes), at which point the exit status is
+/// reaped. This replaces the `waitpid(child, None)` blocking pattern with
+/// something a supervisor can `await` alongside many other children in one task.
+#[derive(Debug)]
+pub(crate) struct ChildChannel {
+    control: AsyncPipeSendRecv,
+    pid: unistd::Pid,
+    wait: WaitStrategy,
+}
+
+impl ChildChannel {
+    /// Wraps `control`, the channel to `pid`'s control pipe, and opens a pidfd for
+    /// `pid` to back `wait()`.
+    pub(crate) fn new(control: AsyncPipeSendRecv, pid: unistd::Pid) -> Result<Self> {
+        let wait = match pidfd_open(pid) {
+            Ok(fd) => {
+                // Safety: `fd` was just returned by `pidfd_open(2)`, owned by
+                // nobody else yet.
+                let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+                WaitStrategy::PidFd(AsyncFd::new(fd)?)
+            }
+            Err(e) if e.raw_os_error() == Some(nix::libc::ENOSYS) => WaitStrategy::BlockingThread,
+            Err(e) => return Err(e),
+        };
+        Ok(ChildChannel { control, pid, wait })
+    }
+
+    /// Sends a control message to the child. See `AsyncPipeSendRecv::send`.
+    pub(crate) async fn send<T: Serialize>(&mut self, item: T) -> Result<()> {
+        self.control.send(item).await
+    }
+
+    /// Receives a control message from the child. See `AsyncPipeSendRecv::recv`.
+    pub(crate) async fn recv<T: DeserializeOwned>(&mut self) -> Result<T> {
+        self.control.recv().await
+    }
+
+    /// Resolves once the child has exited, reaping and returning its status.
+    pub(crate) async fn wait(&mut self) -> Result<nix::sys::wait::WaitStatus> {
+        match &mut self.wait {
+            WaitStrategy::PidFd(fd) => {
+                fd.readable().await?.clear_ready();
+                nix::sys::wait::waitpid(self.pid, Some(nix::sys::wait::WaitPidFlag::WNOHANG))
+                    .map_err(from_nix)
+            }
+            WaitStrategy::BlockingThread => {
+                let pid = self.pid;
+                tokio::task::spawn_blocking(move || {
+                    nix::sys::wait::waitpid(pid, None).map_err(from_nix)
+                })
+                .await
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+            }
+        }
+    }
+}
+
 /// Send an item with bincode default serialization on self
 pub(crate) trait PipeSend {
     fn send<T: Serialize>(&mut self, item: T) -> Result<()>;
@@ -229,6 +752,276 @@ pub(crate) fn pipe_duplex<R: io::Read, S: io::Write>(
     Ok((left, right))
 }
 
+/// Maximum number of bytes accepted for a single `recv_with_fds` message.
+const SEQPACKET_MAX_MESSAGE_SIZE: usize = 4096;
+
+/// Maximum number of fds accepted for a single `recv_with_fds` message.
+const SEQPACKET_MAX_FDS: usize = 16;
+
+/// Opens a `SOCK_SEQPACKET` socketpair(2): unlike `pipe_duplex`, a single fd pair
+/// is bidirectional and preserves message boundaries (one `send` == one
+/// `recvmsg`), which also lets it carry fds alongside a message via
+/// `send_with_fds`/`recv_with_fds`.
+#[allow(dead_code)]
+pub(crate) fn seqpacket_duplex() -> Result<(SeqPacket, SeqPacket)> {
+    let (left, right) = socket::socketpair(
+        AddressFamily::Unix,
+        SockType::SeqPacket,
+        None,
+        SockFlag::SOCK_CLOEXEC,
+    )
+    .map_err(from_nix)?;
+    // Safety: `left`/`right` are fresh fds just returned by `socketpair(2)`,
+    // owned by nobody else yet.
+    unsafe {
+        Ok((
+            SeqPacket {
+                fd: OwnedFd::from_raw_fd(left),
+            },
+            SeqPacket {
+                fd: OwnedFd::from_raw_fd(right),
+            },
+        ))
+    }
+}
+
+/// One end of a `SOCK_SEQPACKET` socketpair(2)
+///
+/// The fd is owned via `OwnedFd`, so closing and double-close safety are handled
+/// by the standard library instead of a hand-written `Drop`.
+#[derive(Debug)]
+pub(crate) struct SeqPacket {
+    fd: OwnedFd,
+}
+
+impl SeqPacket {
+    /// Sends `payload` verbatim, passing `fds` as ancillary `SCM_RIGHTS` data.
+    fn send_raw(&self, payload: &[u8], fds: &[RawFd]) -> Result<()> {
+        let iov = [IoVec::from_slice(payload)];
+        let cmsgs = if fds.is_empty() {
+            vec![]
+        } else {
+            vec![ControlMessage::ScmRights(fds)]
+        };
+        socket::sendmsg(self.fd.as_raw_fd(), &iov, &cmsgs, MsgFlags::empty(), None)
+            .map_err(from_nix)?;
+        Ok(())
+    }
+
+    /// Receives one message, returning its raw payload and any fds passed
+    /// alongside it via `SCM_RIGHTS`.
+    fn recv_raw(&self) -> Result<(Vec<u8>, ReceivedFds)> {
+        let mut buf = vec![0u8; SEQPACKET_MAX_MESSAGE_SIZE];
+        let mut cmsg_buffer = nix::cmsg_space!([RawFd; SEQPACKET_MAX_FDS]);
+        let iov = [IoVec::from_mut_slice(&mut buf)];
+        let msg = socket::recvmsg(
+            self.fd.as_raw_fd(),
+            &iov,
+            Some(&mut cmsg_buffer),
+            MsgFlags::MSG_CMSG_CLOEXEC,
+        )
+        .map_err(from_nix)?;
+
+        // `recvmsg` silently truncates a message that doesn't fit `buf` or fds
+        // that don't fit `cmsg_buffer` rather than erroring, so a truncated
+        // payload or a dropped fd must be caught here instead of surfacing
+        // later as an opaque bincode error or a leaked fd.
+        if msg.flags.contains(MsgFlags::MSG_TRUNC) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "received message exceeds SEQPACKET_MAX_MESSAGE_SIZE and was truncated",
+            ));
+        }
+        if msg.flags.contains(MsgFlags::MSG_CTRUNC) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "received message carries more than SEQPACKET_MAX_FDS and was truncated",
+            ));
+        }
+
+        let mut fds = vec![];
+        for cmsg in msg.cmsgs() {
+            if let ControlMessageOwned::ScmRights(received) = cmsg {
+                // Safety: `received` fds were just handed to us by `recvmsg(2)`
+                // via `SCM_RIGHTS`, owned by nobody else yet.
+                fds.extend(received.into_iter().map(|fd| unsafe { OwnedFd::from_raw_fd(fd) }));
+            }
+        }
+
+        buf.truncate(msg.bytes);
+        Ok((buf, ReceivedFds(fds)))
+    }
+
+    /// Sends `item` serialized with bincode, passing `fds` as ancillary
+    /// `SCM_RIGHTS` data so they are handed over atomically with the message.
+    pub(crate) fn send_with_fds<T: Serialize>(&self, item: T, fds: &[RawFd]) -> Result<()> {
+        let payload =
+            bincode::serialize(&item).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        self.send_raw(&payload, fds)
+    }
+
+    /// Receives a message sent with `send_with_fds`, returning the deserialized
+    /// payload and any fds that were passed alongside it.
+    ///
+    /// Received fds are wrapped in [`ReceivedFds`] and are closed on drop unless
+    /// the caller takes them with `ReceivedFds::into_raw_fds`.
+    pub(crate) fn recv_with_fds<T: DeserializeOwned>(&self) -> Result<(T, ReceivedFds)> {
+        let (payload, fds) = self.recv_raw()?;
+        let item = bincode::deserialize(&payload)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        Ok((item, fds))
+    }
+}
+
+impl io::Read for SeqPacket {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        unistd::read(self.fd.as_raw_fd(), buf).map_err(from_nix)
+    }
+}
+
+impl io::Write for SeqPacket {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        unistd::write(self.fd.as_raw_fd(), buf).map_err(from_nix)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl AsRawFd for SeqPacket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl AsFd for SeqPacket {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+impl IntoRawFd for SeqPacket {
+    fn into_raw_fd(self) -> RawFd {
+        self.fd.into_raw_fd()
+    }
+}
+
+/// Fds received via `SeqPacket::recv_with_fds`.
+///
+/// Owned via `OwnedFd`, so they are closed on drop unless the caller takes
+/// them with `into_raw_fds` - a message whose fds the receiver ignores
+/// doesn't leak them.
+#[derive(Debug)]
+pub(crate) struct ReceivedFds(Vec<OwnedFd>);
+
+impl ReceivedFds {
+    /// Takes ownership of the received fds, so they are no longer closed on drop.
+    pub(crate) fn into_raw_fds(mut self) -> Vec<RawFd> {
+        mem::take(&mut self.0)
+            .into_iter()
+            .map(OwnedFd::into_raw_fd)
+            .collect()
+    }
+}
+
+/// Asynchronous `SOCK_SEQPACKET` endpoint, mirroring `AsyncPipeRead`/
+/// `AsyncPipeWrite` but bidirectional over a single fd, as `SeqPacket` is.
+#[derive(Debug)]
+pub(crate) struct AsyncSeqPacket {
+    inner: AsyncFd<SeqPacket>,
+}
+
+impl TryFrom<SeqPacket> for AsyncSeqPacket {
+    type Error = io::Error;
+
+    fn try_from(seqpacket: SeqPacket) -> Result<Self> {
+        seqpacket.set_nonblocking();
+        Ok(AsyncSeqPacket {
+            inner: AsyncFd::new(seqpacket)?,
+        })
+    }
+}
+
+impl AsyncSeqPacket {
+    /// Async version of `SeqPacket::send_with_fds`.
+    pub(crate) async fn send_with_fds<T: Serialize>(&self, item: T, fds: &[RawFd]) -> Result<()> {
+        let payload =
+            bincode::serialize(&item).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        loop {
+            let mut guard = self.inner.writable().await?;
+            match guard.try_io(|inner| inner.get_ref().send_raw(&payload, fds)) {
+                Ok(result) => return result,
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    /// Async version of `SeqPacket::recv_with_fds`.
+    pub(crate) async fn recv_with_fds<T: DeserializeOwned>(&self) -> Result<(T, ReceivedFds)> {
+        loop {
+            let mut guard = self.inner.readable().await?;
+            match guard.try_io(|inner| inner.get_ref().recv_raw()) {
+                Ok(Ok((payload, fds))) => {
+                    let item = bincode::deserialize(&payload)
+                        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+                    return Ok((item, fds));
+                }
+                Ok(Err(e)) => return Err(e),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+}
+
+impl AsyncRead for AsyncSeqPacket {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<()>> {
+        loop {
+            let mut guard = ready!(self.inner.poll_read_ready(cx))?;
+            match guard.try_io(|inner| {
+                let fd = inner.get_ref().as_raw_fd();
+                match unistd::read(fd, buf.initialized_mut()) {
+                    Ok(n) => Ok(n),
+                    Err(e) => Err(from_nix(e)),
+                }
+            }) {
+                Ok(Ok(n)) => {
+                    buf.advance(n);
+                    return Poll::Ready(Ok(()));
+                }
+                Ok(Err(e)) => {
+                    return Poll::Ready(Err(e));
+                }
+                Err(_would_block) => continue,
+            }
+        }
+    }
+}
+
+impl AsyncWrite for AsyncSeqPacket {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        loop {
+            let mut guard = ready!(self.inner.poll_write_ready(cx))?;
+            match guard.try_io(|inner| unistd::write(inner.as_raw_fd(), buf).map_err(from_nix)) {
+                Ok(result) => return Poll::Ready(result),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
 /// Duplex message passing
 pub trait PipeSendRecv {
     fn recv<T: Serialize + DeserializeOwned>(&mut self) -> Result<T>;
@@ -279,7 +1072,6 @@ mod tests {
         process, thread, time,
     };
     use time::Duration;
-    use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
     #[test]
     /// Smoke test
@@ -309,6 +1101,30 @@ mod tests {
         assert_eq!(&buf, "Hello");
     }
 
+    #[test]
+    /// `into_raw_fd` must hand over the fd without running `PipeRead`'s drop glue,
+    /// so closing it afterwards must not be a double-close
+    fn into_raw_fd_no_double_close() {
+        let (read, _write) = pipe().unwrap();
+        let fd = read.into_raw_fd();
+        unistd::close(fd).unwrap();
+    }
+
+    #[test]
+    /// A fd taken out via `into_raw_fd` must be usable again after being adopted
+    /// with `from_raw_fd`
+    fn from_raw_fd_roundtrip() {
+        let (read, mut write) = pipe().unwrap();
+        let fd = read.into_raw_fd();
+        let mut adopted = unsafe { PipeRead::from_raw_fd(fd) };
+
+        write.write(b"Hello").unwrap();
+
+        let mut buf = [0u8; 5];
+        adopted.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"Hello");
+    }
+
     #[test]
     #[should_panic]
     /// Dropping the write end must reault in an EOF
@@ -370,6 +1186,120 @@ mod tests {
         write.await.unwrap()
     }
 
+    #[cfg(feature = "io-uring")]
+    #[tokio::test]
+    /// Exercise the io_uring backend: same shape as `r#async`, but built with
+    /// `Backend::IoUring` rather than the default `AsyncFd` path
+    async fn uring() {
+        let (read, write) = pipe().unwrap();
+
+        let mut read = AsyncPipeRead::with_backend(read, Backend::IoUring).unwrap();
+        let mut write = AsyncPipeWrite::with_backend(write, Backend::IoUring).unwrap();
+
+        let write = tokio::spawn(async move {
+            for n in 0..=65535u32 {
+                write.write_all(&n.to_be_bytes()).await.unwrap();
+            }
+        });
+
+        let mut buf = [0u8; 4];
+        for n in 0..=65535u32 {
+            read.read_exact(&mut buf).await.unwrap();
+            assert_eq!(buf, n.to_be_bytes());
+        }
+
+        write.await.unwrap()
+    }
+
+    #[cfg(feature = "io-uring")]
+    #[tokio::test]
+    /// A single completion can fill more of the ring's internal buffer than a
+    /// caller's `ReadBuf` has room for; `UringRead` must carry the remainder
+    /// over to the next poll instead of panicking in `ReadBuf::put_slice` or
+    /// discarding it.
+    async fn uring_drains_buffered_data_across_short_reads() {
+        let (read, mut write) = pipe().unwrap();
+
+        // Queue more bytes than a single 4-byte read can hold before the
+        // uring backend ever polls, so the first completion is guaranteed to
+        // return more than `buf.remaining()`.
+        let payload: Vec<u8> = (0..=255u8).collect();
+        write.write_all(&payload).unwrap();
+        drop(write);
+
+        let mut read = AsyncPipeRead::with_backend(read, Backend::IoUring).unwrap();
+
+        let mut received = Vec::new();
+        let mut buf = [0u8; 4];
+        loop {
+            let n = read.read(&mut buf).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            received.extend_from_slice(&buf[..n]);
+        }
+
+        assert_eq!(received, payload);
+    }
+
+    #[tokio::test]
+    /// Length-delimited send/recv must survive partial reads on a pipe
+    async fn async_send_recv() -> Result<()> {
+        let (read_left, write_left) = pipe().unwrap();
+        let (read_right, write_right) = pipe().unwrap();
+        let mut left = AsyncPipeSendRecv::new(read_left.try_into().unwrap(), write_right.try_into().unwrap());
+        let mut right = AsyncPipeSendRecv::new(read_right.try_into().unwrap(), write_left.try_into().unwrap());
+
+        for n in 0..100u32 {
+            left.send(n).await?;
+            assert_eq!(right.recv::<u32>().await?, n);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    /// A length prefix beyond the max frame size must be rejected without
+    /// allocating
+    async fn async_recv_rejects_oversized_frame() {
+        let (read, write) = pipe().unwrap();
+        let (_unused_read, dummy_write) = pipe().unwrap();
+        let mut write: AsyncPipeWrite = write.try_into().unwrap();
+        let mut recv = AsyncPipeSendRecv::new(read.try_into().unwrap(), dummy_write.try_into().unwrap());
+
+        write
+            .write_all(&(ASYNC_PIPE_MAX_FRAME_SIZE + 1).to_le_bytes())
+            .await
+            .unwrap();
+
+        let err = recv.recv::<u32>().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    /// A payload beyond the max frame size must be rejected by `send` itself,
+    /// rather than writing a frame the peer's `recv` is guaranteed to reject
+    async fn async_send_rejects_oversized_frame() {
+        let (read, write) = pipe().unwrap();
+        let mut send = AsyncPipeSendRecv::new(read.try_into().unwrap(), write.try_into().unwrap());
+
+        let oversized = vec![0u8; ASYNC_PIPE_MAX_FRAME_SIZE as usize + 1];
+        let err = send.send(oversized).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    /// Dropping the write end at a message boundary must surface as a clean EOF
+    async fn async_recv_eof() {
+        let (read, write) = pipe().unwrap();
+        let (_unused_read, dummy_write) = pipe().unwrap();
+        let mut recv = AsyncPipeSendRecv::new(read.try_into().unwrap(), dummy_write.try_into().unwrap());
+        drop(write);
+
+        let err = recv.recv::<u32>().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
     #[test]
     /// Fork test
     fn fork() {
@@ -508,4 +1438,107 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    /// A `ChildChannel` must be able to talk to its forked child over the control
+    /// channel and observe its exit via `wait`
+    async fn child_channel() -> Result<()> {
+        let (parent_end, child_end) = super::pipe_duplex::<PipeRead, PipeWrite>()?;
+
+        match unsafe { unistd::fork().unwrap() } {
+            unistd::ForkResult::Parent { child: pid } => {
+                drop(child_end);
+                let (read, write) = parent_end;
+                let read: AsyncPipeRead = read.try_into().unwrap();
+                let write: AsyncPipeWrite = write.try_into().unwrap();
+                let mut channel = ChildChannel::new(AsyncPipeSendRecv::new(read, write), pid)?;
+
+                channel.send(42i32).await?;
+                assert_eq!(channel.recv::<i32>().await?, 42);
+
+                channel.wait().await?;
+            }
+            unistd::ForkResult::Child => {
+                drop(parent_end);
+                let (mut read, mut write) = child_end;
+
+                // The child never touches the tokio reactor (unsafe across
+                // fork); it speaks `AsyncPipeSendRecv`'s length-prefixed wire
+                // format with plain blocking reads/writes instead.
+                let mut len_buf = [0u8; 4];
+                read.read_exact(&mut len_buf).unwrap();
+                let len = u32::from_le_bytes(len_buf) as usize;
+                let mut payload = vec![0u8; len];
+                read.read_exact(&mut payload).unwrap();
+                let n: i32 = bincode::deserialize(&payload).unwrap();
+
+                let payload = bincode::serialize(&n).unwrap();
+                write
+                    .write_all(&(payload.len() as u32).to_le_bytes())
+                    .unwrap();
+                write.write_all(&payload).unwrap();
+                process::exit(0);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    /// Smoke test message sending and receiving over a SEQPACKET socketpair
+    fn seqpacket_send_recv() -> Result<()> {
+        let (mut left, mut right) = super::seqpacket_duplex()?;
+
+        for n in 0..100 {
+            left.send(n)?;
+            assert_eq!(right.recv::<i32>()?, n);
+
+            right.send(n)?;
+            assert_eq!(left.recv::<i32>()?, n);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    /// Fds passed alongside a message must arrive usable on the other end and
+    /// be handed over by `into_raw_fds`
+    fn seqpacket_send_recv_fds() -> Result<()> {
+        let (left, right) = super::seqpacket_duplex()?;
+
+        let (passed_read, mut passed_write) = pipe()?;
+        let passed_fd = passed_read.as_raw_fd();
+        left.send_with_fds(1234, &[passed_fd])?;
+        drop(passed_read);
+
+        let (item, fds) = right.recv_with_fds::<i32>()?;
+        assert_eq!(item, 1234);
+
+        let received = fds.into_raw_fds();
+        assert_eq!(received.len(), 1);
+
+        passed_write.write(b"hi")?;
+        let mut buf = [0u8; 2];
+        unistd::read(received[0], &mut buf).unwrap();
+        assert_eq!(&buf, b"hi");
+
+        unistd::close(received[0]).ok();
+        Ok(())
+    }
+
+    #[tokio::test]
+    /// Test the async SEQPACKET endpoint
+    async fn seqpacket_async() -> Result<()> {
+        let (left, right) = super::seqpacket_duplex()?;
+        let left: AsyncSeqPacket = left.try_into().unwrap();
+        let right: AsyncSeqPacket = right.try_into().unwrap();
+
+        for n in 0..100 {
+            left.send_with_fds(n, &[]).await?;
+            let (item, _) = right.recv_with_fds::<i32>().await?;
+            assert_eq!(item, n);
+        }
+
+        Ok(())
+    }
 }